@@ -1,8 +1,17 @@
 /// A module dedicated to the ancient art of quantum spaghetti entanglement.
 /// WARNING: Do not run this near actual pasta. Results may be delicious but undefined.
 
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::cmp::Reverse;
+
+use ndarray::Array1;
+use num_complex::Complex;
+use rand::Rng;
+use sha2::{Digest, Sha256};
+
+/// Number of basis states a noodle's wavefunction is expanded over, one per
+/// `NoodleState` variant.
+const BASIS_STATES: usize = 3;
 
 /// Represents a single strand of quantum spaghetti
 #[derive(Debug, Clone)]
@@ -11,6 +20,17 @@ pub struct QuantumNoodle {
     pub sauce_entanglement: Vec<SauceParticle>,
     pub al_dente_coefficient: u128,
     pub existential_crisis: bool,
+    /// Basis-state amplitudes. Invariant: `amplitudes.iter().map(|a| a.norm_sqr()).sum() == 1.0`.
+    amplitudes: Array1<Complex<f64>>,
+}
+
+/// Computes the Kronecker (tensor) product of two amplitude vectors: entry
+/// `i * b.len() + j` is `a[i] * b[j]`, the amplitude of joint outcome `(i, j)`.
+fn kronecker_product(a: &Array1<Complex<f64>>, b: &Array1<Complex<f64>>) -> Array1<Complex<f64>> {
+    Array1::from_shape_fn(a.len() * b.len(), |k| {
+        let (i, j) = (k / b.len(), k % b.len());
+        a[i] * b[j]
+    })
 }
 
 /// The fundamental particle of marinara
@@ -27,11 +47,72 @@ pub struct SchrodingerColander<T> {
     maybe_holes: Option<Vec<T>>,
     is_observed: bool,
     pasta_wavefunction: Box<dyn Fn(f64) -> QuantumNoodle>,
+    /// The noodle the wavefunction collapsed to, memoized on first `observe`.
+    cached_noodle: Option<QuantumNoodle>,
+}
+
+impl<T> SchrodingerColander<T> {
+    /// Creates a colander in superposition: the noodle isn't real until
+    /// someone looks.
+    pub fn new(maybe_holes: Option<Vec<T>>, pasta_wavefunction: Box<dyn Fn(f64) -> QuantumNoodle>) -> Self {
+        Self { maybe_holes, is_observed: false, pasta_wavefunction, cached_noodle: None }
+    }
+
+    /// Observes the colander. The first call evaluates `pasta_wavefunction`
+    /// and memoizes the result; every subsequent call, regardless of `param`,
+    /// returns that same cached noodle — a snack stays in superposition only
+    /// until it's first observed.
+    pub fn observe(&mut self, param: f64) -> &QuantumNoodle {
+        if !self.is_observed {
+            self.cached_noodle = Some((self.pasta_wavefunction)(param));
+            self.is_observed = true;
+        }
+        self.cached_noodle.as_ref().expect("is_observed implies cached_noodle is populated")
+    }
+
+    /// Peeks at the collapsed noodle without forcing an observation.
+    /// Returns `None` while the colander is still in superposition.
+    pub fn peek(&self) -> Option<&QuantumNoodle> {
+        self.cached_noodle.as_ref()
+    }
+
+    /// Drops the cached noodle and restores superposition.
+    pub fn reset(&mut self) {
+        self.cached_noodle = None;
+        self.is_observed = false;
+    }
 }
 
 impl QuantumNoodle {
-    /// Creates a noodle that simultaneously exists and doesn't
+    /// Builds a noodle from raw basis amplitudes, normalizing onto the unit
+    /// sphere so `Σ|a_i|² = 1`. Rejects amplitude vectors whose norm is zero
+    /// or non-finite — that's not superposition, that's just broken pasta.
+    pub fn from_amplitudes(amplitudes: Array1<Complex<f64>>) -> Result<Self, PastaError> {
+        let norm_sq: f64 = amplitudes.iter().map(|a| a.norm_sqr()).sum();
+        if !norm_sq.is_finite() || norm_sq <= 0.0 {
+            return Err(PastaError::SauceDecoherence);
+        }
+        let norm = norm_sq.sqrt();
+        let amplitudes = amplitudes.mapv(|a| a / norm);
+
+        Ok(Self {
+            wobble_factor: 42.0 / 0.0_f64.sin().cos().tan(),
+            sauce_entanglement: vec![
+                SauceParticle::VoidSauce,
+                SauceParticle::Marinara { spiciness: f64::INFINITY },
+            ],
+            al_dente_coefficient: 0xDEADBEEF_CAFEBABE,
+            existential_crisis: true,
+            amplitudes,
+        })
+    }
+
+    /// Creates a noodle that simultaneously exists and doesn't: an equal-weight
+    /// superposition over every basis state (`1/√n` each).
     pub fn superposition() -> Self {
+        let equal_weight = 1.0 / (BASIS_STATES as f64).sqrt();
+        let amplitudes = Array1::from_elem(BASIS_STATES, Complex::new(equal_weight, 0.0));
+
         Self {
             wobble_factor: 42.0 / 0.0_f64.sin().cos().tan(),
             sauce_entanglement: vec![
@@ -40,6 +121,7 @@ impl QuantumNoodle {
             ],
             al_dente_coefficient: 0xDEADBEEF_CAFEBABE,
             existential_crisis: true,
+            amplitudes,
         }
     }
 
@@ -53,22 +135,68 @@ impl QuantumNoodle {
         let combined_wobble = self.wobble_factor * other.wobble_factor;
         other.al_dente_coefficient = self.al_dente_coefficient ^ other.al_dente_coefficient;
 
+        // The joint amplitude vector is the tensor product of both noodles,
+        // so a later measurement over it picks one outcome pair `(i, j)`.
+        // The product state is separable: the two outcomes are independent.
+        let joint_amplitudes = kronecker_product(&self.amplitudes, &other.amplitudes);
+
         Ok(SpaghettiVortex {
             angular_meatball_momentum: combined_wobble,
             noodle_count: usize::MAX, // it's a lot of noodles
             is_spinning: true,
+            joint_amplitudes,
         })
     }
 
-    /// Measures the noodle, collapsing its wavefunction into either
-    /// "overcooked" or "still crunchy in the middle somehow"
-    pub fn measure(&self) -> NoodleState {
-        match self.al_dente_coefficient % 3 {
-            0 => NoodleState::PerfectlyAlDente,
-            1 => NoodleState::OvercookedIntoOblivion,
-            2 => NoodleState::SomehowFrozenAndBurning,
-            _ => unreachable!("math has ceased to function"),
+    /// Measures the noodle via the Born rule: draws a uniform `r ∈ [0,1)`,
+    /// walks the cumulative sum of `|a_i|²` to pick an outcome index, then
+    /// collapses the stored amplitude vector to that one-hot basis state.
+    pub fn measure(&mut self) -> NoodleState {
+        let outcome = sample_born_rule(&self.amplitudes);
+        self.amplitudes = collapse_to_basis_state(self.amplitudes.len(), outcome);
+        outcome_to_state(outcome)
+    }
+}
+
+/// Walks the cumulative sum of `|a_i|²` until it exceeds `r`, picking the
+/// corresponding basis-state index. `r` is expected to be drawn (or derived)
+/// uniformly from `[0,1)`.
+fn pick_outcome(amplitudes: &Array1<Complex<f64>>, r: f64) -> usize {
+    let mut cumulative = 0.0;
+    let mut outcome = amplitudes.len() - 1;
+    for (i, amp) in amplitudes.iter().enumerate() {
+        cumulative += amp.norm_sqr();
+        if r < cumulative {
+            outcome = i;
+            break;
+        }
+    }
+    outcome
+}
+
+/// Samples a basis-state index via the Born rule using true randomness.
+fn sample_born_rule(amplitudes: &Array1<Complex<f64>>) -> usize {
+    let r: f64 = rand::thread_rng().gen_range(0.0..1.0);
+    pick_outcome(amplitudes, r)
+}
+
+/// Builds a one-hot amplitude vector for a collapsed basis state.
+fn collapse_to_basis_state(len: usize, outcome: usize) -> Array1<Complex<f64>> {
+    Array1::from_shape_fn(len, |i| {
+        if i == outcome {
+            Complex::new(1.0, 0.0)
+        } else {
+            Complex::new(0.0, 0.0)
         }
+    })
+}
+
+fn outcome_to_state(outcome: usize) -> NoodleState {
+    match outcome {
+        0 => NoodleState::PerfectlyAlDente,
+        1 => NoodleState::OvercookedIntoOblivion,
+        2 => NoodleState::SomehowFrozenAndBurning,
+        _ => unreachable!("math has ceased to function"),
     }
 }
 
@@ -86,6 +214,8 @@ pub struct SpaghettiVortex {
     pub angular_meatball_momentum: f64,
     pub noodle_count: usize,
     pub is_spinning: bool,
+    /// The tensor product of the two entangled noodles' amplitude vectors.
+    pub joint_amplitudes: Array1<Complex<f64>>,
 }
 
 /// Things that can go wrong in quantum pasta physics
@@ -96,48 +226,419 @@ pub enum PastaError {
     NoodleCollapsedIntoBlackHole,
     ForkEntangledWithSpoon,
     RanOutOfParmesan,
+    MalformedQuery(String),
+}
+
+/// Opaque handle to a noodle entity. Stable across ticks even as the noodle's
+/// components change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NoodleId(u64);
+
+/// Per-entity data, laid out one map per component so systems can read just
+/// the slice of state they care about. `temperature`, `positions`, `goals`
+/// and `plans` are optional: not every noodle has one.
+#[derive(Default)]
+pub struct ComponentStores {
+    pub names: HashMap<NoodleId, String>,
+    pub wobble_factor: HashMap<NoodleId, f64>,
+    pub amplitudes: HashMap<NoodleId, Array1<Complex<f64>>>,
+    pub sauce: HashMap<NoodleId, Vec<SauceParticle>>,
+    pub al_dente_coefficient: HashMap<NoodleId, u128>,
+    pub existential_crisis: HashMap<NoodleId, bool>,
+    pub temperature: HashMap<NoodleId, f64>,
+    pub positions: HashMap<NoodleId, Point>,
+    pub goals: HashMap<NoodleId, NoodleGoal>,
+    pub plans: HashMap<NoodleId, Vec<Point>>,
+}
+
+/// A single cell on the kitchen grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+/// A goal a noodle agent can pursue across the kitchen grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoodleGoal {
+    Reach(Point),
+    Idle,
+    FleeBlackHole(Point),
+}
+
+/// A 2D kitchen floor plan. Walls and sauce spills block noodle movement.
+pub struct KitchenGrid {
+    width: i32,
+    height: i32,
+    blocked: HashSet<Point>,
+}
+
+impl KitchenGrid {
+    pub fn new(width: i32, height: i32) -> Self {
+        Self { width, height, blocked: HashSet::new() }
+    }
+
+    /// Marks a cell impassable (a wall or a sauce spill, the kitchen doesn't
+    /// discriminate).
+    pub fn block(&mut self, point: Point) {
+        self.blocked.insert(point);
+    }
+
+    fn in_bounds(&self, point: Point) -> bool {
+        point.x >= 0 && point.x < self.width && point.y >= 0 && point.y < self.height
+    }
+
+    fn is_passable(&self, point: Point) -> bool {
+        self.in_bounds(point) && !self.blocked.contains(&point)
+    }
+
+    fn neighbors(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        [
+            Point { x: point.x + 1, y: point.y },
+            Point { x: point.x - 1, y: point.y },
+            Point { x: point.x, y: point.y + 1 },
+            Point { x: point.x, y: point.y - 1 },
+        ]
+        .into_iter()
+        .filter(move |&p| self.is_passable(p))
+    }
+}
+
+fn manhattan_distance(a: Point, b: Point) -> i32 {
+    (a.x - b.x).abs() + (a.y - b.y).abs()
+}
+
+/// Finds a 4-neighbor path from `start` to `goal` via A* with a
+/// Manhattan-distance heuristic. Returns an empty path (never panics) when
+/// `goal` is unreachable — callers must treat that as "stay idle".
+fn astar(grid: &KitchenGrid, start: Point, goal: Point) -> Vec<Point> {
+    if start == goal || !grid.is_passable(goal) {
+        return Vec::new();
+    }
+
+    let mut open = BinaryHeap::new();
+    open.push(Reverse((manhattan_distance(start, goal), 0i32, start)));
+    let mut came_from: HashMap<Point, Point> = HashMap::new();
+    let mut cost_so_far: HashMap<Point, i32> = HashMap::new();
+    cost_so_far.insert(start, 0);
+
+    while let Some(Reverse((_, cost, current))) = open.pop() {
+        if current == goal {
+            return reconstruct_path(&came_from, start, goal);
+        }
+        for next in grid.neighbors(current) {
+            let new_cost = cost + 1;
+            if cost_so_far.get(&next).map_or(true, |&c| new_cost < c) {
+                cost_so_far.insert(next, new_cost);
+                let priority = new_cost + manhattan_distance(next, goal);
+                open.push(Reverse((priority, new_cost, next)));
+                came_from.insert(next, current);
+            }
+        }
+    }
+
+    Vec::new()
+}
+
+fn reconstruct_path(came_from: &HashMap<Point, Point>, start: Point, goal: Point) -> Vec<Point> {
+    let mut path = vec![goal];
+    let mut current = goal;
+    while current != start {
+        current = came_from[&current];
+        if current != start {
+            path.push(current);
+        }
+    }
+    path.reverse();
+    path
+}
+
+/// Every cell reachable from `start` without crossing a wall or sauce spill.
+fn reachable_cells(grid: &KitchenGrid, start: Point) -> Vec<Point> {
+    let mut visited = HashSet::new();
+    visited.insert(start);
+    let mut frontier = vec![start];
+    let mut all = vec![start];
+
+    while let Some(current) = frontier.pop() {
+        for next in grid.neighbors(current) {
+            if visited.insert(next) {
+                frontier.push(next);
+                all.push(next);
+            }
+        }
+    }
+
+    all
+}
+
+/// A deferred mutation produced by a system. Systems only read
+/// `ComponentStores`; `GUPTEngine::tick` applies the buffered commands in
+/// order once every system has run.
+pub enum PastaCommand {
+    SpawnNoodle(String, QuantumNoodle),
+    DespawnNoodle(NoodleId),
+    AdjustMeatballs(i32),
+    CollapseAmplitude(NoodleId, Array1<Complex<f64>>),
+    SetPosition(NoodleId, Point),
+    SetPlan(NoodleId, Vec<Point>),
+    EmitEvent(PastaEvent),
+}
+
+/// A simulation system: reads component state plus this tick's VDF output
+/// and emits the commands it wants applied. Systems never mutate
+/// `ComponentStores` directly, which is what lets users plug in their own
+/// without fighting the engine's own iteration. Most systems ignore the
+/// entropy; `measurement_system` is the one that needs it.
+pub type PastaSystem = fn(&ComponentStores, &[u8; 32]) -> Vec<PastaCommand>;
+
+/// Default system: derives a deterministic `r ∈ [0,1)` per noodle from this
+/// tick's VDF output and samples the Born rule with it, so the same seed
+/// always produces the same events. Noodles are processed in name order
+/// (never `HashMap` iteration order) so which noodle consumes which slice of
+/// entropy doesn't depend on hashing.
+fn measurement_system(components: &ComponentStores, entropy: &[u8; 32]) -> Vec<PastaCommand> {
+    let mut commands = Vec::new();
+
+    let mut ids: Vec<NoodleId> = components.amplitudes.keys().copied().collect();
+    ids.sort_by_key(|id| (components.names.get(id).cloned().unwrap_or_default(), *id));
+
+    for (index, id) in ids.into_iter().enumerate() {
+        let amplitudes = &components.amplitudes[&id];
+        let name = components.names.get(&id).cloned().unwrap_or_default();
+        let r = deterministic_unit_interval(entropy, index);
+        let outcome = pick_outcome(amplitudes, r);
+        commands.push(PastaCommand::CollapseAmplitude(
+            id,
+            collapse_to_basis_state(amplitudes.len(), outcome),
+        ));
+
+        let event = match outcome_to_state(outcome) {
+            NoodleState::PerfectlyAlDente => PastaEvent::ChefKiss(name),
+            NoodleState::OvercookedIntoOblivion => {
+                commands.push(PastaCommand::AdjustMeatballs(-1)); // a meatball weeps
+                PastaEvent::Tragedy(name)
+            }
+            NoodleState::SomehowFrozenAndBurning => {
+                PastaEvent::ParadoxDetected { noodle: name, confusion_level: f64::NAN }
+            }
+        };
+        commands.push(PastaCommand::EmitEvent(event));
+    }
+
+    commands
+}
+
+/// Default system: pops the next step off each noodle's cached plan,
+/// advancing its position, and emits `NoodleArrived` once the last step of a
+/// `Reach` goal (or the chosen flee destination) is consumed.
+fn movement_system(components: &ComponentStores, _entropy: &[u8; 32]) -> Vec<PastaCommand> {
+    let mut commands = Vec::new();
+
+    let mut ids: Vec<NoodleId> = components.plans.keys().copied().collect();
+    ids.sort_by_key(|id| (components.names.get(id).cloned().unwrap_or_default(), *id));
+
+    for id in ids {
+        let plan = &components.plans[&id];
+        let Some((&next, rest)) = plan.split_first() else {
+            continue;
+        };
+        let name = components.names.get(&id).cloned().unwrap_or_default();
+        commands.push(PastaCommand::SetPosition(id, next));
+        commands.push(PastaCommand::SetPlan(id, rest.to_vec()));
+
+        let arrived = match components.goals.get(&id) {
+            Some(NoodleGoal::Reach(target)) => *target == next,
+            Some(NoodleGoal::FleeBlackHole(_)) => rest.is_empty(),
+            _ => false,
+        };
+        if arrived {
+            commands.push(PastaCommand::EmitEvent(PastaEvent::NoodleArrived(name)));
+        }
+    }
+
+    commands
+}
+
+/// A proof that a verifiable delay function ran for `iterations` sequential
+/// hash steps, yielding `output`. Lets a caller confirm a recorded tick
+/// happened without re-simulating the noodles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TickProof {
+    pub output: [u8; 32],
+    pub iterations: u32,
+}
+
+/// Applies the sequential hash chain `y_{i+1} = H(y_i)`, starting from
+/// `seed`, for `iterations` steps. `iterations` must be at least 1 — a VDF
+/// with zero steps proves nothing, which `GUPTEngine` enforces by clamping
+/// on construction.
+fn run_vdf(seed: [u8; 32], iterations: u32) -> [u8; 32] {
+    let mut y = seed;
+    for _ in 0..iterations {
+        let mut hasher = Sha256::new();
+        hasher.update(y);
+        y = hasher.finalize().into();
+    }
+    y
+}
+
+/// Derives a uniform `r ∈ [0,1)` for the `index`-th noodle (in sorted name
+/// order) from a tick's VDF output, so measurement is reproducible from the
+/// seed alone instead of relying on floating-point hashing.
+fn deterministic_unit_interval(output: &[u8; 32], index: usize) -> f64 {
+    let mut hasher = Sha256::new();
+    hasher.update(output);
+    hasher.update(index.to_le_bytes());
+    let digest: [u8; 32] = hasher.finalize().into();
+    let bits = u64::from_le_bytes(digest[0..8].try_into().unwrap());
+    ((bits >> 11) as f64) / ((1u64 << 53) as f64)
 }
 
 /// The Grand Unified Pasta Theory (GUPT) engine
 pub struct GUPTEngine {
-    noodle_registry: HashMap<String, Arc<QuantumNoodle>>,
+    next_id: u64,
+    name_index: HashMap<String, NoodleId>,
+    components: ComponentStores,
+    systems: Vec<PastaSystem>,
     sauce_field_strength: f64,
     meatball_count: i32, // can go negative in antimatter kitchens
+    /// The evolving VDF seed. Every tick derives its entropy from this and
+    /// then replaces it with the tick's output, so ticks form an auditable
+    /// hash chain.
+    seed: [u8; 32],
+    /// `T`: how many sequential hash steps each tick's VDF runs. Always >= 1.
+    vdf_iterations: u32,
 }
 
 impl GUPTEngine {
     pub fn new() -> Self {
+        Self::with_seed_and_iterations([0u8; 32], 16)
+    }
+
+    /// Creates an engine with an explicit VDF seed and iteration count `T`.
+    /// `T` is clamped to at least 1: a zero-iteration VDF would prove
+    /// nothing.
+    pub fn with_seed_and_iterations(seed: [u8; 32], iterations: u32) -> Self {
         Self {
-            noodle_registry: HashMap::new(),
+            next_id: 0,
+            name_index: HashMap::new(),
+            components: ComponentStores::default(),
+            systems: vec![measurement_system, movement_system],
             sauce_field_strength: 9.81, // gravity of the situation
             meatball_count: 42,
+            seed,
+            vdf_iterations: iterations.max(1),
         }
     }
 
-    /// Simulates the entire pasta universe for one tick
+    /// The current seed, i.e. the output of the most recent tick (or the
+    /// construction-time seed if no tick has run yet).
+    pub fn seed(&self) -> [u8; 32] {
+        self.seed
+    }
+
+    /// Registers a system to run on every future tick, in addition to the
+    /// built-in measurement and movement systems. Lets callers extend the simulation
+    /// without touching the engine.
+    pub fn register_system(&mut self, system: PastaSystem) {
+        self.systems.push(system);
+    }
+
+    /// Simulates the entire pasta universe for one tick. Paths are planned,
+    /// then a VDF derives this tick's entropy from the current seed, every
+    /// registered system reads `ComponentStores` plus that entropy and emits
+    /// commands, and the buffered commands are applied in order to produce
+    /// the final state and events. The seed is then advanced to the VDF's
+    /// output, so replaying from the same starting seed reproduces the same
+    /// events.
     /// Time complexity: O(delicious)
-    pub fn tick(&mut self) -> Vec<PastaEvent> {
-        let mut events = Vec::new();
+    pub fn tick(&mut self, grid: &KitchenGrid) -> (Vec<PastaEvent>, TickProof) {
+        self.plan_paths(grid);
+
+        let output = run_vdf(self.seed, self.vdf_iterations);
+        let proof = TickProof { output, iterations: self.vdf_iterations };
+
+        let mut commands = Vec::new();
+        for system in self.systems.clone() {
+            commands.extend(system(&self.components, &output));
+        }
 
-        for (name, noodle) in &self.noodle_registry {
-            match noodle.measure() {
-                NoodleState::PerfectlyAlDente => {
-                    events.push(PastaEvent::ChefKiss(name.clone()));
+        let mut events = Vec::new();
+        for command in commands {
+            match command {
+                PastaCommand::SpawnNoodle(name, noodle) => {
+                    self.spawn_noodle(name, noodle);
+                }
+                PastaCommand::DespawnNoodle(id) => self.despawn_noodle(id),
+                PastaCommand::AdjustMeatballs(delta) => self.meatball_count += delta,
+                PastaCommand::CollapseAmplitude(id, amplitude) => {
+                    self.components.amplitudes.insert(id, amplitude);
                 }
-                NoodleState::OvercookedIntoOblivion => {
-                    self.meatball_count -= 1; // a meatball weeps
-                    events.push(PastaEvent::Tragedy(name.clone()));
+                PastaCommand::SetPosition(id, position) => {
+                    self.components.positions.insert(id, position);
                 }
-                NoodleState::SomehowFrozenAndBurning => {
-                    events.push(PastaEvent::ParadoxDetected {
-                        noodle: name.clone(),
-                        confusion_level: f64::NAN,
-                    });
+                PastaCommand::SetPlan(id, plan) => {
+                    self.components.plans.insert(id, plan);
                 }
+                PastaCommand::EmitEvent(event) => events.push(event),
             }
         }
 
-        events
+        self.seed = output;
+        (events, proof)
+    }
+
+    /// Re-runs the `T`-step hash chain from `prev_seed` and checks it
+    /// reproduces `proof.output`, confirming a recorded tick happened
+    /// without re-simulating the noodles.
+    pub fn verify_tick(prev_seed: [u8; 32], proof: &TickProof) -> bool {
+        proof.iterations >= 1 && run_vdf(prev_seed, proof.iterations) == proof.output
+    }
+
+    /// Plans a path for every noodle with a pending goal and no cached plan.
+    /// Invoked at the start of `tick`, before measurement, so the movement
+    /// system has a plan ready to pop a step from. An unreachable goal just
+    /// leaves the plan empty — the noodle stays put instead of panicking.
+    pub fn plan_paths(&mut self, grid: &KitchenGrid) {
+        let pending: Vec<NoodleId> = self
+            .components
+            .goals
+            .keys()
+            .copied()
+            .filter(|id| self.components.plans.get(id).map_or(true, |p| p.is_empty()))
+            .collect();
+
+        for id in pending {
+            let position = *self.components.positions.entry(id).or_insert(Point { x: 0, y: 0 });
+            let plan = match self.components.goals[&id] {
+                NoodleGoal::Idle => Vec::new(),
+                NoodleGoal::Reach(target) => astar(grid, position, target),
+                NoodleGoal::FleeBlackHole(hazard) => reachable_cells(grid, position)
+                    .into_iter()
+                    .max_by_key(|&p| manhattan_distance(p, hazard))
+                    .map(|target| astar(grid, position, target))
+                    .unwrap_or_default(),
+            };
+            self.components.plans.insert(id, plan);
+        }
+    }
+
+    /// Sets the goal a registered noodle should pursue across the kitchen
+    /// grid. No-op if `name` isn't registered.
+    pub fn set_goal(&mut self, name: &str, goal: NoodleGoal) {
+        if let Some(&id) = self.name_index.get(name) {
+            self.components.goals.insert(id, goal);
+            self.components.plans.remove(&id);
+        }
+    }
+
+    /// Places a registered noodle on the kitchen grid. No-op if `name` isn't
+    /// registered.
+    pub fn set_position(&mut self, name: &str, position: Point) {
+        if let Some(&id) = self.name_index.get(name) {
+            self.components.positions.insert(id, position);
+        }
     }
 
     /// Adds a noodle to the simulation
@@ -146,10 +647,401 @@ impl GUPTEngine {
         if noodle.wobble_factor.is_nan() {
             return false; // NaN noodles are not welcome
         }
-        self.noodle_registry.insert(name, Arc::new(noodle));
-        self.sauce_field_strength *= 1.001; // each noodle strengthens the sauce field
+        self.spawn_noodle(name, noodle);
         true
     }
+
+    fn spawn_noodle(&mut self, name: String, noodle: QuantumNoodle) -> NoodleId {
+        let id = NoodleId(self.next_id);
+        self.next_id += 1;
+
+        self.components.wobble_factor.insert(id, noodle.wobble_factor);
+        self.components.amplitudes.insert(id, noodle.amplitudes.clone());
+        self.components.sauce.insert(id, noodle.sauce_entanglement.clone());
+        self.components.al_dente_coefficient.insert(id, noodle.al_dente_coefficient);
+        self.components.existential_crisis.insert(id, noodle.existential_crisis);
+        self.components.names.insert(id, name.clone());
+        self.name_index.insert(name, id);
+
+        self.sauce_field_strength *= 1.001; // each noodle strengthens the sauce field
+        id
+    }
+
+    fn despawn_noodle(&mut self, id: NoodleId) {
+        if let Some(name) = self.components.names.remove(&id) {
+            self.name_index.remove(&name);
+        }
+        self.components.wobble_factor.remove(&id);
+        self.components.amplitudes.remove(&id);
+        self.components.sauce.remove(&id);
+        self.components.al_dente_coefficient.remove(&id);
+        self.components.existential_crisis.remove(&id);
+        self.components.temperature.remove(&id);
+        self.components.positions.remove(&id);
+        self.components.goals.remove(&id);
+        self.components.plans.remove(&id);
+    }
+
+    /// Runs a tiny query over the noodle registry, e.g.
+    /// `SELECT noodle WHERE wobble > 10 AND sauce CONTAINS Marinara` or
+    /// `COUNT noodle WHERE existential_crisis = true`.
+    pub fn query(&self, src: &str) -> Result<QueryResult, PastaError> {
+        let tokens = Lexer::tokenize(src)?;
+        let ast = Parser::new(tokens).parse_query()?;
+
+        let matching: Vec<String> = self
+            .name_index
+            .iter()
+            .filter(|&(_, &id)| ast.filter.as_ref().map_or(true, |filter| self.matches_filter(id, filter)))
+            .map(|(name, _)| name.clone())
+            .collect();
+
+        Ok(match ast.kind {
+            QueryKind::Select => QueryResult::Names(matching),
+            QueryKind::Count => QueryResult::Count(matching.len()),
+        })
+    }
+
+    fn matches_filter(&self, id: NoodleId, filter: &Filter) -> bool {
+        match filter {
+            Filter::And(lhs, rhs) => self.matches_filter(id, lhs) && self.matches_filter(id, rhs),
+            Filter::Or(lhs, rhs) => self.matches_filter(id, lhs) || self.matches_filter(id, rhs),
+            Filter::Contains { field, value } => match field.as_str() {
+                "sauce" => self.components.sauce.get(&id).is_some_and(|particles| {
+                    particles.iter().any(|p| sauce_variant_name(p).eq_ignore_ascii_case(value))
+                }),
+                _ => false,
+            },
+            Filter::Compare { field, op, value } => self.compare_field(id, field, *op, value),
+        }
+    }
+
+    fn compare_field(&self, id: NoodleId, field: &str, op: CompareOp, value: &FieldValue) -> bool {
+        match (field, value) {
+            ("wobble", FieldValue::Number(n)) => {
+                self.components.wobble_factor.get(&id).is_some_and(|w| apply_compare(*w, op, *n))
+            }
+            ("temperature", FieldValue::Number(n)) => {
+                self.components.temperature.get(&id).is_some_and(|t| apply_compare(*t, op, *n))
+            }
+            ("existential_crisis", FieldValue::Bool(b)) => {
+                self.components.existential_crisis.get(&id).is_some_and(|crisis| match op {
+                    CompareOp::Eq => crisis == b,
+                    CompareOp::Neq => crisis != b,
+                    _ => false,
+                })
+            }
+            _ => false,
+        }
+    }
+}
+
+fn apply_compare(lhs: f64, op: CompareOp, rhs: f64) -> bool {
+    match op {
+        CompareOp::Gt => lhs > rhs,
+        CompareOp::Lt => lhs < rhs,
+        CompareOp::Gte => lhs >= rhs,
+        CompareOp::Lte => lhs <= rhs,
+        CompareOp::Eq => lhs == rhs,
+        CompareOp::Neq => lhs != rhs,
+    }
+}
+
+fn sauce_variant_name(particle: &SauceParticle) -> &'static str {
+    match particle {
+        SauceParticle::Marinara { .. } => "Marinara",
+        SauceParticle::Alfredo { .. } => "Alfredo",
+        SauceParticle::Pesto { .. } => "Pesto",
+        SauceParticle::VoidSauce => "VoidSauce",
+    }
+}
+
+/// A single lexical token in the GUPT query language.
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Select,
+    Count,
+    Where,
+    And,
+    Or,
+    Contains,
+    Identifier(String),
+    Number(f64),
+    Bool(bool),
+    Text(String),
+    Gt,
+    Lt,
+    Gte,
+    Lte,
+    Eq,
+    Neq,
+}
+
+/// Turns query source text into a token stream.
+struct Lexer<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(src: &'a str) -> Self {
+        Self { chars: src.chars().peekable() }
+    }
+
+    fn tokenize(src: &str) -> Result<Vec<Token>, PastaError> {
+        let mut lexer = Lexer::new(src);
+        let mut tokens = Vec::new();
+        while let Some(token) = lexer.next_token()? {
+            tokens.push(token);
+        }
+        Ok(tokens)
+    }
+
+    fn next_token(&mut self) -> Result<Option<Token>, PastaError> {
+        while matches!(self.chars.peek(), Some(c) if c.is_whitespace()) {
+            self.chars.next();
+        }
+
+        let Some(&c) = self.chars.peek() else {
+            return Ok(None);
+        };
+
+        if c.is_alphabetic() || c == '_' {
+            let mut word = String::new();
+            while matches!(self.chars.peek(), Some(c) if c.is_alphanumeric() || *c == '_') {
+                word.push(self.chars.next().unwrap());
+            }
+            return Ok(Some(match word.to_ascii_uppercase().as_str() {
+                "SELECT" => Token::Select,
+                "COUNT" => Token::Count,
+                "WHERE" => Token::Where,
+                "AND" => Token::And,
+                "OR" => Token::Or,
+                "CONTAINS" => Token::Contains,
+                "TRUE" => Token::Bool(true),
+                "FALSE" => Token::Bool(false),
+                _ => Token::Identifier(word),
+            }));
+        }
+
+        if c.is_ascii_digit() {
+            let mut number = String::new();
+            while matches!(self.chars.peek(), Some(c) if c.is_ascii_digit() || *c == '.') {
+                number.push(self.chars.next().unwrap());
+            }
+            let value: f64 = number
+                .parse()
+                .map_err(|_| PastaError::MalformedQuery(format!("'{number}' is not a number")))?;
+            return Ok(Some(Token::Number(value)));
+        }
+
+        if c == '"' {
+            self.chars.next();
+            let mut text = String::new();
+            loop {
+                match self.chars.next() {
+                    Some('"') => break,
+                    Some(c) => text.push(c),
+                    None => {
+                        return Err(PastaError::MalformedQuery("unterminated string literal".to_string()));
+                    }
+                }
+            }
+            return Ok(Some(Token::Text(text)));
+        }
+
+        match c {
+            '>' => {
+                self.chars.next();
+                if self.chars.peek() == Some(&'=') {
+                    self.chars.next();
+                    Ok(Some(Token::Gte))
+                } else {
+                    Ok(Some(Token::Gt))
+                }
+            }
+            '<' => {
+                self.chars.next();
+                if self.chars.peek() == Some(&'=') {
+                    self.chars.next();
+                    Ok(Some(Token::Lte))
+                } else {
+                    Ok(Some(Token::Lt))
+                }
+            }
+            '=' => {
+                self.chars.next();
+                Ok(Some(Token::Eq))
+            }
+            '!' => {
+                self.chars.next();
+                if self.chars.next() == Some('=') {
+                    Ok(Some(Token::Neq))
+                } else {
+                    Err(PastaError::MalformedQuery("expected '=' after '!'".to_string()))
+                }
+            }
+            other => Err(PastaError::MalformedQuery(format!("unexpected character '{other}'"))),
+        }
+    }
+}
+
+/// What a query should produce once filtered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum QueryKind {
+    Select,
+    Count,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CompareOp {
+    Gt,
+    Lt,
+    Gte,
+    Lte,
+    Eq,
+    Neq,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum FieldValue {
+    Number(f64),
+    Bool(bool),
+    Text(String),
+}
+
+/// A parsed filter predicate, evaluated per-noodle.
+#[derive(Debug, Clone)]
+enum Filter {
+    Compare { field: String, op: CompareOp, value: FieldValue },
+    Contains { field: String, value: String },
+    And(Box<Filter>, Box<Filter>),
+    Or(Box<Filter>, Box<Filter>),
+}
+
+/// The parsed form of a GUPT query.
+#[derive(Debug, Clone)]
+pub struct QueryAst {
+    kind: QueryKind,
+    filter: Option<Filter>,
+}
+
+/// The outcome of running a GUPT query.
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryResult {
+    Names(Vec<String>),
+    Count(usize),
+}
+
+/// Recursive-descent parser: `query := (SELECT | COUNT) noodle (WHERE or_expr)?`.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn new(tokens: Vec<Token>) -> Self {
+        Self { tokens, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn expect_identifier(&mut self, what: &str) -> Result<String, PastaError> {
+        match self.advance() {
+            Some(Token::Identifier(name)) => Ok(name),
+            other => Err(PastaError::MalformedQuery(format!("expected {what}, found {other:?}"))),
+        }
+    }
+
+    fn parse_query(&mut self) -> Result<QueryAst, PastaError> {
+        let kind = match self.advance() {
+            Some(Token::Select) => QueryKind::Select,
+            Some(Token::Count) => QueryKind::Count,
+            other => {
+                return Err(PastaError::MalformedQuery(format!("expected SELECT or COUNT, found {other:?}")));
+            }
+        };
+
+        let target = self.expect_identifier("'noodle'")?;
+        if !target.eq_ignore_ascii_case("noodle") {
+            return Err(PastaError::MalformedQuery(format!("expected 'noodle', found '{target}'")));
+        }
+
+        let filter = if self.peek() == Some(&Token::Where) {
+            self.advance();
+            Some(self.parse_or()?)
+        } else {
+            None
+        };
+
+        if let Some(trailing) = self.peek() {
+            return Err(PastaError::MalformedQuery(format!("unexpected trailing token {trailing:?}")));
+        }
+
+        Ok(QueryAst { kind, filter })
+    }
+
+    fn parse_or(&mut self) -> Result<Filter, PastaError> {
+        let mut lhs = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.advance();
+            let rhs = self.parse_and()?;
+            lhs = Filter::Or(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<Filter, PastaError> {
+        let mut lhs = self.parse_condition()?;
+        while self.peek() == Some(&Token::And) {
+            self.advance();
+            let rhs = self.parse_condition()?;
+            lhs = Filter::And(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_condition(&mut self) -> Result<Filter, PastaError> {
+        let field = self.expect_identifier("a field name")?;
+
+        match self.advance() {
+            Some(Token::Contains) => {
+                let value = self.expect_identifier("a value")?;
+                Ok(Filter::Contains { field, value })
+            }
+            Some(op_token) => {
+                let op = match op_token {
+                    Token::Gt => CompareOp::Gt,
+                    Token::Lt => CompareOp::Lt,
+                    Token::Gte => CompareOp::Gte,
+                    Token::Lte => CompareOp::Lte,
+                    Token::Eq => CompareOp::Eq,
+                    Token::Neq => CompareOp::Neq,
+                    other => {
+                        return Err(PastaError::MalformedQuery(format!(
+                            "expected a comparison operator, found {other:?}"
+                        )));
+                    }
+                };
+                let value = match self.advance() {
+                    Some(Token::Number(n)) => FieldValue::Number(n),
+                    Some(Token::Bool(b)) => FieldValue::Bool(b),
+                    Some(Token::Text(s)) => FieldValue::Text(s),
+                    Some(Token::Identifier(s)) => FieldValue::Text(s),
+                    other => return Err(PastaError::MalformedQuery(format!("expected a value, found {other:?}"))),
+                };
+                Ok(Filter::Compare { field, op, value })
+            }
+            None => Err(PastaError::MalformedQuery(format!("expected an operator after '{field}'"))),
+        }
+    }
 }
 
 /// Events that occur in the pasta simulation
@@ -160,6 +1052,7 @@ pub enum PastaEvent {
     ParadoxDetected { noodle: String, confusion_level: f64 },
     MeatballEscapeVelocityReached,
     GarlicBreadSingularity,
+    NoodleArrived(String),
 }
 
 #[cfg(test)]
@@ -185,4 +1078,194 @@ mod tests {
         let engine = GUPTEngine::new();
         assert_eq!(engine.meatball_count, 42, "the answer to everything is meatballs");
     }
+
+    #[test]
+    fn test_tick_emits_one_event_per_registered_noodle() {
+        let mut engine = GUPTEngine::new();
+        engine.register_noodle("fusilli".to_string(), QuantumNoodle::superposition());
+        engine.register_noodle("penne".to_string(), QuantumNoodle::superposition());
+
+        let grid = KitchenGrid::new(4, 4);
+        let (events, _proof) = engine.tick(&grid);
+        assert_eq!(events.len(), 2, "the measurement system should emit one event per noodle");
+    }
+
+    #[test]
+    fn test_custom_system_can_despawn_a_noodle() {
+        fn despawn_all(components: &ComponentStores, _entropy: &[u8; 32]) -> Vec<PastaCommand> {
+            components.names.keys().map(|&id| PastaCommand::DespawnNoodle(id)).collect()
+        }
+
+        let mut engine = GUPTEngine::new();
+        engine.register_noodle("rigatoni".to_string(), QuantumNoodle::superposition());
+        engine.register_system(despawn_all);
+
+        let grid = KitchenGrid::new(4, 4);
+        engine.tick(&grid);
+        assert!(engine.components.names.is_empty(), "a registered system must be able to despawn noodles");
+    }
+
+    #[test]
+    fn test_superposition_is_normalized() {
+        let noodle = QuantumNoodle::superposition();
+        let norm_sq: f64 = noodle.amplitudes.iter().map(|a| a.norm_sqr()).sum();
+        assert!((norm_sq - 1.0).abs() < 1e-9, "a noodle's wavefunction must stay normalized");
+    }
+
+    #[test]
+    fn test_from_amplitudes_rejects_zero_norm() {
+        let zero = Array1::from_elem(BASIS_STATES, Complex::new(0.0, 0.0));
+        let result = QuantumNoodle::from_amplitudes(zero);
+        assert!(matches!(result, Err(PastaError::SauceDecoherence)));
+    }
+
+    #[test]
+    fn test_measure_collapses_to_one_hot() {
+        let mut noodle = QuantumNoodle::superposition();
+        noodle.measure();
+        let ones = noodle.amplitudes.iter().filter(|a| a.norm_sqr() > 0.5).count();
+        assert_eq!(ones, 1, "measurement must collapse the noodle to a single basis state");
+    }
+
+    #[test]
+    fn test_colander_peek_is_none_until_observed() {
+        let colander: SchrodingerColander<()> =
+            SchrodingerColander::new(None, Box::new(|_| QuantumNoodle::superposition()));
+        assert!(colander.peek().is_none(), "an unobserved colander has nothing to peek at");
+    }
+
+    #[test]
+    fn test_colander_observe_memoizes_first_result() {
+        let mut colander: SchrodingerColander<()> =
+            SchrodingerColander::new(None, Box::new(|param| {
+                let mut noodle = QuantumNoodle::superposition();
+                noodle.wobble_factor = param;
+                noodle
+            }));
+
+        let first = colander.observe(1.0).wobble_factor;
+        let second = colander.observe(2.0).wobble_factor;
+        assert_eq!(first, second, "a snack stays in superposition only until first observed");
+    }
+
+    #[test]
+    fn test_colander_reset_restores_superposition() {
+        let mut colander: SchrodingerColander<()> =
+            SchrodingerColander::new(None, Box::new(|_| QuantumNoodle::superposition()));
+
+        colander.observe(1.0);
+        colander.reset();
+        assert!(colander.peek().is_none(), "reset must drop the cached noodle");
+    }
+
+    #[test]
+    fn test_astar_finds_path_around_a_wall() {
+        let mut grid = KitchenGrid::new(3, 3);
+        grid.block(Point { x: 1, y: 0 });
+        grid.block(Point { x: 1, y: 1 });
+
+        let path = astar(&grid, Point { x: 0, y: 0 }, Point { x: 2, y: 0 });
+        assert_eq!(path.last(), Some(&Point { x: 2, y: 0 }));
+        assert!(!path.contains(&Point { x: 1, y: 0 }), "the path must not cross a blocked cell");
+    }
+
+    #[test]
+    fn test_astar_returns_empty_when_unreachable() {
+        let mut grid = KitchenGrid::new(3, 3);
+        grid.block(Point { x: 1, y: 0 });
+        grid.block(Point { x: 0, y: 1 });
+
+        let path = astar(&grid, Point { x: 0, y: 0 }, Point { x: 2, y: 2 });
+        assert!(path.is_empty(), "an unreachable goal must leave the noodle idle, not panic");
+    }
+
+    #[test]
+    fn test_noodle_reaches_goal_and_emits_arrival_event() {
+        let mut engine = GUPTEngine::new();
+        engine.register_noodle("linguine".to_string(), QuantumNoodle::superposition());
+        engine.set_position("linguine", Point { x: 0, y: 0 });
+        engine.set_goal("linguine", NoodleGoal::Reach(Point { x: 2, y: 0 }));
+
+        let grid = KitchenGrid::new(3, 1);
+        let mut arrived = false;
+        for _ in 0..5 {
+            let (events, _proof) = engine.tick(&grid);
+            if events.iter().any(|e| matches!(e, PastaEvent::NoodleArrived(name) if name == "linguine")) {
+                arrived = true;
+                break;
+            }
+        }
+        assert!(arrived, "a noodle pursuing a reachable goal should eventually arrive");
+    }
+
+    #[test]
+    fn test_query_select_filters_by_wobble_and_sauce() {
+        let mut engine = GUPTEngine::new();
+        engine.register_noodle("fettuccine".to_string(), QuantumNoodle::superposition());
+
+        let result = engine
+            .query("SELECT noodle WHERE wobble > 10 AND sauce CONTAINS Marinara")
+            .expect("a well-formed query should parse");
+        assert_eq!(result, QueryResult::Names(vec!["fettuccine".to_string()]));
+    }
+
+    #[test]
+    fn test_query_count_filters_by_existential_crisis() {
+        let mut engine = GUPTEngine::new();
+        engine.register_noodle("orzo".to_string(), QuantumNoodle::superposition());
+
+        let result = engine
+            .query("COUNT noodle WHERE existential_crisis = true")
+            .expect("a well-formed query should parse");
+        assert_eq!(result, QueryResult::Count(1));
+    }
+
+    #[test]
+    fn test_query_rejects_malformed_syntax() {
+        let engine = GUPTEngine::new();
+        let result = engine.query("SELECT noodle WHERE wobble >");
+        assert!(matches!(result, Err(PastaError::MalformedQuery(_))));
+    }
+
+    #[test]
+    fn test_same_seed_produces_same_events() {
+        let grid = KitchenGrid::new(4, 4);
+
+        let mut engine_a = GUPTEngine::with_seed_and_iterations([7u8; 32], 4);
+        engine_a.register_noodle("bucatini".to_string(), QuantumNoodle::superposition());
+        let (events_a, proof_a) = engine_a.tick(&grid);
+
+        let mut engine_b = GUPTEngine::with_seed_and_iterations([7u8; 32], 4);
+        engine_b.register_noodle("bucatini".to_string(), QuantumNoodle::superposition());
+        let (events_b, proof_b) = engine_b.tick(&grid);
+
+        assert_eq!(proof_a, proof_b, "the same seed must yield the same VDF output");
+        assert_eq!(
+            format!("{events_a:?}"),
+            format!("{events_b:?}"),
+            "the same seed must reproduce the same events"
+        );
+    }
+
+    #[test]
+    fn test_verify_tick_confirms_a_recorded_tick() {
+        let mut engine = GUPTEngine::with_seed_and_iterations([1u8; 32], 8);
+        let prev_seed = engine.seed();
+        let grid = KitchenGrid::new(2, 2);
+
+        let (_events, proof) = engine.tick(&grid);
+        assert!(GUPTEngine::verify_tick(prev_seed, &proof));
+        assert_eq!(engine.seed(), proof.output, "the seed must advance to the tick's VDF output");
+    }
+
+    #[test]
+    fn test_verify_tick_rejects_a_tampered_proof() {
+        let mut engine = GUPTEngine::with_seed_and_iterations([2u8; 32], 8);
+        let prev_seed = engine.seed();
+        let grid = KitchenGrid::new(2, 2);
+
+        let (_events, mut proof) = engine.tick(&grid);
+        proof.output[0] ^= 0xFF;
+        assert!(!GUPTEngine::verify_tick(prev_seed, &proof));
+    }
 }